@@ -0,0 +1,145 @@
+//! Python-exposed wrappers around hpke-spec's cipher suite selectors, so
+//! callers can pick a suite other than the hardcoded default.
+
+use hpke::{HPKEConfig, Mode as SpecMode};
+use hpke_aead::AEAD as SpecAEAD;
+use hpke_kdf::KDF as SpecKDF;
+use hpke_kem::KEM as SpecKEM;
+use pyo3::prelude::*;
+
+/// RFC 9180 base/auth/psk/auth_psk mode selector.
+#[pyclass(name = "Mode")]
+#[derive(Clone, Copy)]
+pub struct Mode(pub(crate) SpecMode);
+
+#[pymethods]
+impl Mode {
+    #[staticmethod]
+    fn base() -> Self {
+        Mode(SpecMode::mode_base)
+    }
+
+    #[staticmethod]
+    fn psk() -> Self {
+        Mode(SpecMode::mode_psk)
+    }
+
+    #[staticmethod]
+    fn auth() -> Self {
+        Mode(SpecMode::mode_auth)
+    }
+
+    #[staticmethod]
+    fn auth_psk() -> Self {
+        Mode(SpecMode::mode_auth_psk)
+    }
+}
+
+/// KEM algorithm selector.
+#[pyclass(name = "KEM")]
+#[derive(Clone, Copy)]
+pub struct KEM(pub(crate) SpecKEM);
+
+#[pymethods]
+impl KEM {
+    #[staticmethod]
+    fn dhkem_p256_hkdf_sha256() -> Self {
+        KEM(SpecKEM::DHKEM_P256_HKDF_SHA256)
+    }
+
+    #[staticmethod]
+    fn dhkem_p384_hkdf_sha384() -> Self {
+        KEM(SpecKEM::DHKEM_P384_HKDF_SHA384)
+    }
+
+    #[staticmethod]
+    fn dhkem_p521_hkdf_sha512() -> Self {
+        KEM(SpecKEM::DHKEM_P521_HKDF_SHA512)
+    }
+
+    #[staticmethod]
+    fn dhkem_x25519_hkdf_sha256() -> Self {
+        KEM(SpecKEM::DHKEM_X25519_HKDF_SHA256)
+    }
+
+    #[staticmethod]
+    fn dhkem_x448_hkdf_sha512() -> Self {
+        KEM(SpecKEM::DHKEM_X448_HKDF_SHA512)
+    }
+}
+
+/// KDF algorithm selector.
+#[pyclass(name = "KDF")]
+#[derive(Clone, Copy)]
+pub struct KDF(pub(crate) SpecKDF);
+
+#[pymethods]
+impl KDF {
+    #[staticmethod]
+    fn hkdf_sha256() -> Self {
+        KDF(SpecKDF::HKDF_SHA256)
+    }
+
+    #[staticmethod]
+    fn hkdf_sha384() -> Self {
+        KDF(SpecKDF::HKDF_SHA384)
+    }
+
+    #[staticmethod]
+    fn hkdf_sha512() -> Self {
+        KDF(SpecKDF::HKDF_SHA512)
+    }
+}
+
+/// AEAD algorithm selector.
+#[pyclass(name = "AEAD")]
+#[derive(Clone, Copy)]
+pub struct AEAD(pub(crate) SpecAEAD);
+
+#[pymethods]
+impl AEAD {
+    #[staticmethod]
+    fn aes_128_gcm() -> Self {
+        AEAD(SpecAEAD::AES128GCM)
+    }
+
+    #[staticmethod]
+    fn aes_256_gcm() -> Self {
+        AEAD(SpecAEAD::AES256GCM)
+    }
+
+    #[staticmethod]
+    fn chacha20_poly1305() -> Self {
+        AEAD(SpecAEAD::ChaCha20Poly1305)
+    }
+}
+
+/// The HPKE cipher suite (mode, KEM, KDF, AEAD) a call should use.
+#[pyclass(name = "HPKEConfig")]
+#[derive(Clone, Copy)]
+pub struct PyHPKEConfig(pub(crate) HPKEConfig);
+
+#[pymethods]
+impl PyHPKEConfig {
+    #[new]
+    fn new(mode: Mode, kem: KEM, kdf: KDF, aead: AEAD) -> Self {
+        PyHPKEConfig(HPKEConfig(mode.0, kem.0, kdf.0, aead.0))
+    }
+
+    /// Mirrors the crate's previous hardcoded default: Base mode,
+    /// DHKEM(X25519, HKDF-SHA256), HKDF-SHA256, ChaCha20Poly1305.
+    #[staticmethod]
+    #[allow(clippy::should_implement_trait)]
+    fn default() -> Self {
+        PyHPKEConfig(default_hpke_config())
+    }
+}
+
+pub fn default_hpke_config() -> HPKEConfig {
+    HPKEConfig(
+        SpecMode::mode_base,
+        SpecKEM::DHKEM_X25519_HKDF_SHA256,
+        SpecKDF::HKDF_SHA256,
+        SpecAEAD::ChaCha20Poly1305,
+    )
+}
@@ -0,0 +1,262 @@
+//! `SenderContext`/`ReceiverContext`: hpke-spec's `SetupBaseS`/`SetupBaseR`
+//! run once to agree on a key schedule, after which many messages can be
+//! sealed/opened without repeating key encapsulation.
+
+use hacspec_lib::{Seq, U8};
+use hpke::{
+    AdditionalData, ContextExport, HPKEConfig, HpkePrivateKey, HpkePublicKey, Mode, SetupBaseR,
+    SetupBaseS,
+};
+use hpke_aead::{Open as AeadOpen, Seal as AeadSeal};
+use hpke_errors::HpkeError;
+use hpke_kdf::Info;
+use hpke_kem::Randomness;
+use pyo3::exceptions::{PyOverflowError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::random_bytes_32;
+
+fn map_hpke_error(hpke_error: HpkeError) -> PyErr {
+    PyRuntimeError::new_err(format!("{hpke_error:?}"))
+}
+
+/// Computes `base_nonce XOR seq`, per RFC 9180's per-message nonce derivation.
+fn nonce_for_seq(base_nonce: &[u8], seq: u64) -> Vec<u8> {
+    let mut nonce = base_nonce.to_vec();
+    let seq_bytes = seq.to_be_bytes();
+    let offset = nonce.len().saturating_sub(seq_bytes.len());
+    for (i, byte) in seq_bytes.iter().enumerate() {
+        nonce[offset + i] ^= byte;
+    }
+    nonce
+}
+
+struct KeySchedule {
+    key: Vec<u8>,
+    base_nonce: Vec<u8>,
+    exporter_secret: Vec<u8>,
+}
+
+impl KeySchedule {
+    /// Derives the nonce for the current sequence number, then advances it.
+    /// The final `u64::MAX` nonce is still usable; only the call *after*
+    /// that one sees the overflow, since `sequence_number` becomes `None`.
+    fn next_nonce_and_advance(&self, sequence_number: &mut Option<u64>) -> PyResult<Vec<u8>> {
+        let seq = sequence_number
+            .ok_or_else(|| PyOverflowError::new_err("HPKE context sequence number overflowed"))?;
+        let nonce = nonce_for_seq(&self.base_nonce, seq);
+        *sequence_number = seq.checked_add(1);
+        Ok(nonce)
+    }
+}
+
+/// Only Base mode context setup is implemented so far; reject configs that
+/// claim Auth/PSK/AuthPSK so callers don't silently get unauthenticated Base
+/// key scheduling from a config that promised otherwise.
+fn require_base_mode(hpke_config: HPKEConfig) -> PyResult<()> {
+    if !matches!(hpke_config.0, Mode::mode_base) {
+        return Err(PyValueError::new_err(
+            "setup_sender/setup_receiver only support Mode.base() configs",
+        ));
+    }
+    Ok(())
+}
+
+/// Derives an independent secret from a context's exporter secret via the
+/// KDF's labeled-expand with the "sec" label, per RFC 9180's `Context.Export`.
+fn export_bytes(
+    hpke_config: HPKEConfig,
+    exporter_secret: &[u8],
+    exporter_contextb: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, HpkeError> {
+    let exporter_secret = Seq::<U8>::from_public_slice(exporter_secret);
+    let exporter_context = Seq::<U8>::from_public_slice(exporter_contextb);
+    let exported = ContextExport(hpke_config, &exporter_secret, &exporter_context, length)?;
+    Ok(exported.into_native())
+}
+
+/// Shared `SenderContext`/`ReceiverContext` implementation of `export`:
+/// derives an independent `length`-byte secret bound to `exporter_context`,
+/// e.g. for a separate channel or a MAC, without a second key exchange.
+fn export<'p>(
+    py: Python<'p>,
+    hpke_config: HPKEConfig,
+    schedule: &KeySchedule,
+    exporter_context: &PyBytes,
+    length: usize,
+) -> PyResult<&'p PyBytes> {
+    let exported = export_bytes(
+        hpke_config,
+        &schedule.exporter_secret,
+        exporter_context.as_bytes(),
+        length,
+    )
+    .map_err(map_hpke_error)?;
+    Ok(PyBytes::new(py, &exported))
+}
+
+fn setup_sender_bytes(
+    hpke_config: HPKEConfig,
+    pkb: &[u8],
+    infob: &[u8],
+) -> Result<(Vec<u8>, KeySchedule), HpkeError> {
+    let pk = HpkePublicKey::from_public_slice(pkb);
+    let info = Info::from_public_slice(infob);
+    let randomness = Randomness::from_public_slice(&random_bytes_32());
+    let (enc, key, base_nonce, exporter_secret) =
+        SetupBaseS(hpke_config, &pk, &info, randomness)?;
+    Ok((
+        enc.into_native(),
+        KeySchedule {
+            key: key.into_native(),
+            base_nonce: base_nonce.into_native(),
+            exporter_secret: exporter_secret.into_native(),
+        },
+    ))
+}
+
+fn setup_receiver_bytes(
+    hpke_config: HPKEConfig,
+    encb: &[u8],
+    skb: &[u8],
+    infob: &[u8],
+) -> Result<KeySchedule, HpkeError> {
+    let enc = Seq::<U8>::from_public_slice(encb);
+    let sk = HpkePrivateKey::from_public_slice(skb);
+    let info = Info::from_public_slice(infob);
+    let (key, base_nonce, exporter_secret) = SetupBaseR(hpke_config, &enc, &sk, &info)?;
+    Ok(KeySchedule {
+        key: key.into_native(),
+        base_nonce: base_nonce.into_native(),
+        exporter_secret: exporter_secret.into_native(),
+    })
+}
+
+/// A sender-side HPKE context established via `setup_sender`.
+///
+/// `seal` advances an internal sequence number on every call, so messages
+/// must be sent (and received) in order.
+#[pyclass]
+pub struct SenderContext {
+    config: HPKEConfig,
+    schedule: KeySchedule,
+    sequence_number: Option<u64>,
+}
+
+#[pymethods]
+impl SenderContext {
+    fn seal<'p>(
+        &mut self,
+        py: Python<'p>,
+        aad: &PyBytes,
+        ptxt: &PyBytes,
+    ) -> PyResult<&'p PyBytes> {
+        let nonce = self
+            .schedule
+            .next_nonce_and_advance(&mut self.sequence_number)?;
+        let key = Seq::<U8>::from_public_slice(&self.schedule.key);
+        let nonce = Seq::<U8>::from_public_slice(&nonce);
+        let aad = AdditionalData::from_public_slice(aad.as_bytes());
+        let ptxt = Seq::<U8>::from_public_slice(ptxt.as_bytes());
+        let ciphertext =
+            AeadSeal(self.config.3, &key, &nonce, &aad, &ptxt).map_err(map_hpke_error)?;
+        Ok(PyBytes::new(py, &ciphertext.into_native()))
+    }
+
+    fn export<'p>(
+        &self,
+        py: Python<'p>,
+        exporter_context: &PyBytes,
+        length: usize,
+    ) -> PyResult<&'p PyBytes> {
+        export(py, self.config, &self.schedule, exporter_context, length)
+    }
+}
+
+/// A receiver-side HPKE context established via `setup_receiver`.
+///
+/// `open` advances an internal sequence number on every call, so messages
+/// must be received (and sent) in order.
+#[pyclass]
+pub struct ReceiverContext {
+    config: HPKEConfig,
+    schedule: KeySchedule,
+    sequence_number: Option<u64>,
+}
+
+#[pymethods]
+impl ReceiverContext {
+    fn open<'p>(
+        &mut self,
+        py: Python<'p>,
+        aad: &PyBytes,
+        ctxt: &PyBytes,
+    ) -> PyResult<&'p PyBytes> {
+        let nonce = self
+            .schedule
+            .next_nonce_and_advance(&mut self.sequence_number)?;
+        let key = Seq::<U8>::from_public_slice(&self.schedule.key);
+        let nonce = Seq::<U8>::from_public_slice(&nonce);
+        let aad = AdditionalData::from_public_slice(aad.as_bytes());
+        let ctxt = Seq::<U8>::from_public_slice(ctxt.as_bytes());
+        let ptxt = AeadOpen(self.config.3, &key, &nonce, &aad, &ctxt).map_err(map_hpke_error)?;
+        Ok(PyBytes::new(py, &ptxt.into_native()))
+    }
+
+    fn export<'p>(
+        &self,
+        py: Python<'p>,
+        exporter_context: &PyBytes,
+        length: usize,
+    ) -> PyResult<&'p PyBytes> {
+        export(py, self.config, &self.schedule, exporter_context, length)
+    }
+}
+
+/// Runs hpke-spec's `SetupBaseS` once and returns `(enc, SenderContext)`:
+/// `enc` is the KEM encapsulation the receiver needs for `setup_receiver`,
+/// and the context can then `seal` a stream of messages without re-running
+/// key encapsulation for each one.
+#[pyfunction]
+pub fn setup_sender<'p>(
+    py: Python<'p>,
+    config: crate::config::PyHPKEConfig,
+    pk: &PyBytes,
+    info: &PyBytes,
+) -> PyResult<(&'p PyBytes, SenderContext)> {
+    let hpke_config = config.0;
+    require_base_mode(hpke_config)?;
+    let (enc, schedule) =
+        setup_sender_bytes(hpke_config, pk.as_bytes(), info.as_bytes()).map_err(map_hpke_error)?;
+    Ok((
+        PyBytes::new(py, &enc),
+        SenderContext {
+            config: hpke_config,
+            schedule,
+            sequence_number: Some(0),
+        },
+    ))
+}
+
+/// Runs hpke-spec's `SetupBaseR` once and returns a `ReceiverContext` that
+/// can `open` a stream of messages sealed by the matching `SenderContext`.
+#[pyfunction]
+pub fn setup_receiver(
+    config: crate::config::PyHPKEConfig,
+    enc: &PyBytes,
+    sk: &PyBytes,
+    info: &PyBytes,
+) -> PyResult<ReceiverContext> {
+    let hpke_config = config.0;
+    require_base_mode(hpke_config)?;
+    let schedule =
+        setup_receiver_bytes(hpke_config, enc.as_bytes(), sk.as_bytes(), info.as_bytes())
+            .map_err(map_hpke_error)?;
+    Ok(ReceiverContext {
+        config: hpke_config,
+        schedule,
+        sequence_number: Some(0),
+    })
+}
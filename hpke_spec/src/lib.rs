@@ -1,59 +1,216 @@
+mod config;
+mod context;
+mod mode_args;
+
 use hacspec_lib::{Seq, U8};
-use hpke::{AdditionalData, HPKECiphertext, HPKEConfig, HpkePublicKey, HpkeSeal, Mode};
-use hpke_aead::AEAD;
+use hpke::{
+    AdditionalData, HPKECiphertext, HPKEConfig, HpkeOpen, HpkePrivateKey, HpkePublicKey, HpkeSeal,
+    Psk, PskId,
+};
 use hpke_errors::HpkeError;
-use hpke_kdf::{Info, KDF};
-use hpke_kem::{Randomness, KEM};
+use hpke_kdf::Info;
+use hpke_kem::{GenerateKeyPair, Randomness};
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
 use rand::{rngs::OsRng, RngCore};
 
-fn get_default_hpke_config() -> HPKEConfig {
-    let mode = Mode::mode_base;
-    let kem = KEM::DHKEM_X25519_HKDF_SHA256;
-    let kdf = KDF::HKDF_SHA256;
-    let aead = AEAD::ChaCha20Poly1305;
-    HPKEConfig(mode, kem, kdf, aead)
+use config::{default_hpke_config, PyHPKEConfig, AEAD, KDF, KEM};
+use context::{setup_receiver, setup_sender, ReceiverContext, SenderContext};
+
+pub(crate) fn random_bytes_32() -> [u8; 32] {
+    let mut rand_bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut rand_bytes);
+    rand_bytes
 }
 
-fn hpke_seal_bytes(pkb: &[u8], ptxtb: &[u8]) -> Result<Vec<u8>, HpkeError> {
-    let hpke_config = get_default_hpke_config();
+#[allow(clippy::too_many_arguments)]
+fn hpke_seal_bytes(
+    hpke_config: HPKEConfig,
+    pkb: &[u8],
+    infob: &[u8],
+    aadb: &[u8],
+    ptxtb: &[u8],
+    sender_skb: Option<&[u8]>,
+    pskb: Option<&[u8]>,
+    psk_idb: Option<&[u8]>,
+) -> Result<(Vec<u8>, Vec<u8>), HpkeError> {
     let pk = HpkePublicKey::from_public_slice(pkb);
     let ptxt = Seq::<U8>::from_public_slice(ptxtb);
-    let info = Info::new(0);
-    let aad = AdditionalData::new(0);
-    let mut rand_bytes = [0u8; 32];
-    OsRng.fill_bytes(&mut rand_bytes);
-    let randomness = Randomness::from_public_slice(&rand_bytes);
+    let info = Info::from_public_slice(infob);
+    let aad = AdditionalData::from_public_slice(aadb);
+    let sender_sk = sender_skb.map(HpkePrivateKey::from_public_slice);
+    let psk = pskb.map(Psk::from_public_slice);
+    let psk_id = psk_idb.map(PskId::from_public_slice);
+    let randomness = Randomness::from_public_slice(&random_bytes_32());
     let result: HPKECiphertext = HpkeSeal(
         hpke_config,
         &pk,
         &info,
         &aad,
         &ptxt,
-        None,
-        None,
-        None,
+        sender_sk.as_ref(),
+        psk.as_ref(),
+        psk_id.as_ref(),
         randomness,
     )?;
-    let ciphertext = result.1;
-    Ok(ciphertext.into_native())
+    let (encap, ciphertext) = result;
+    Ok((encap.into_native(), ciphertext.into_native()))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn hpke_open_bytes(
+    hpke_config: HPKEConfig,
+    encb: &[u8],
+    skb: &[u8],
+    infob: &[u8],
+    aadb: &[u8],
+    ctxtb: &[u8],
+    sender_pkb: Option<&[u8]>,
+    pskb: Option<&[u8]>,
+    psk_idb: Option<&[u8]>,
+) -> Result<Vec<u8>, HpkeError> {
+    let enc = Seq::<U8>::from_public_slice(encb);
+    let sk = HpkePrivateKey::from_public_slice(skb);
+    let ctxt = Seq::<U8>::from_public_slice(ctxtb);
+    let info = Info::from_public_slice(infob);
+    let aad = AdditionalData::from_public_slice(aadb);
+    let sender_pk = sender_pkb.map(HpkePublicKey::from_public_slice);
+    let psk = pskb.map(Psk::from_public_slice);
+    let psk_id = psk_idb.map(PskId::from_public_slice);
+    let ptxt = HpkeOpen(
+        hpke_config,
+        &enc,
+        &sk,
+        &info,
+        &aad,
+        &ctxt,
+        sender_pk.as_ref(),
+        psk.as_ref(),
+        psk_id.as_ref(),
+    )?;
+    Ok(ptxt.into_native())
+}
+
+fn generate_key_pair_bytes(hpke_config: HPKEConfig) -> Result<(Vec<u8>, Vec<u8>), HpkeError> {
+    let randomness = Randomness::from_public_slice(&random_bytes_32());
+    let (sk, pk) = GenerateKeyPair(hpke_config.1, randomness)?;
+    Ok((sk.into_native(), pk.into_native()))
+}
+
+/// Python binding to hpke-spec's Single-Shot API function hpke::HpkeSeal.
+///
+/// `info` binds application context to the handshake and `aad` is
+/// authenticated per-message; both are part of RFC 9180's authentication
+/// guarantees. `config` selects the cipher suite and mode, defaulting to
+/// `HPKEConfig.default()` (Base/X25519-HKDF-SHA256/HKDF-SHA256/ChaCha20Poly1305)
+/// when omitted. `sender_sk` is required in Auth and AuthPSK mode, and
+/// `psk`/`psk_id` are required together in PSK and AuthPSK mode. Returns a
+/// `(enc, ciphertext)` tuple: `enc` is the KEM encapsulation that a receiver
+/// needs to pass to `hpke_open` alongside their secret key.
+#[pyfunction]
+#[pyo3(signature = (pk, info, aad, ptxt, config=None, sender_sk=None, psk=None, psk_id=None))]
+#[allow(clippy::too_many_arguments)]
+fn hpke_seal<'p>(
+    py: Python<'p>,
+    pk: &PyBytes,
+    info: &PyBytes,
+    aad: &PyBytes,
+    ptxt: &PyBytes,
+    config: Option<PyHPKEConfig>,
+    sender_sk: Option<&PyBytes>,
+    psk: Option<&PyBytes>,
+    psk_id: Option<&PyBytes>,
+) -> PyResult<(&'p PyBytes, &'p PyBytes)> {
+    let hpke_config = config.map(|c| c.0).unwrap_or_else(default_hpke_config);
+    let sender_skb = sender_sk.map(PyBytes::as_bytes);
+    let pskb = psk.map(PyBytes::as_bytes);
+    let psk_idb = psk_id.map(PyBytes::as_bytes);
+    mode_args::validate(hpke_config.0, sender_skb, pskb, psk_idb)?;
+    let (encap, ciphertext) = hpke_seal_bytes(
+        hpke_config,
+        pk.as_bytes(),
+        info.as_bytes(),
+        aad.as_bytes(),
+        ptxt.as_bytes(),
+        sender_skb,
+        pskb,
+        psk_idb,
+    )
+    .map_err(|hpke_error| PyRuntimeError::new_err(format!("{hpke_error:?}")))?;
+    Ok((PyBytes::new(py, &encap), PyBytes::new(py, &ciphertext)))
+}
+
+/// Python binding to hpke-spec's Single-Shot API function hpke::HpkeOpen.
+///
+/// `info` and `aad` must match the values the sender used in `hpke_seal`,
+/// and `config` must match the cipher suite and mode the sender sealed
+/// with. `sender_pk` is required in Auth and AuthPSK mode (to verify the
+/// sender's identity), and `psk`/`psk_id` are required together in PSK and
+/// AuthPSK mode.
+#[pyfunction]
+#[pyo3(signature = (enc, sk, info, aad, ctxt, config=None, sender_pk=None, psk=None, psk_id=None))]
+#[allow(clippy::too_many_arguments)]
+fn hpke_open<'p>(
+    py: Python<'p>,
+    enc: &PyBytes,
+    sk: &PyBytes,
+    info: &PyBytes,
+    aad: &PyBytes,
+    ctxt: &PyBytes,
+    config: Option<PyHPKEConfig>,
+    sender_pk: Option<&PyBytes>,
+    psk: Option<&PyBytes>,
+    psk_id: Option<&PyBytes>,
+) -> PyResult<&'p PyBytes> {
+    let hpke_config = config.map(|c| c.0).unwrap_or_else(default_hpke_config);
+    let sender_pkb = sender_pk.map(PyBytes::as_bytes);
+    let pskb = psk.map(PyBytes::as_bytes);
+    let psk_idb = psk_id.map(PyBytes::as_bytes);
+    mode_args::validate(hpke_config.0, sender_pkb, pskb, psk_idb)?;
+    let ptxt_bytes = hpke_open_bytes(
+        hpke_config,
+        enc.as_bytes(),
+        sk.as_bytes(),
+        info.as_bytes(),
+        aad.as_bytes(),
+        ctxt.as_bytes(),
+        sender_pkb,
+        pskb,
+        psk_idb,
+    )
+    .map_err(|hpke_error| PyRuntimeError::new_err(format!("{hpke_error:?}")))?;
+    Ok(PyBytes::new(py, &ptxt_bytes))
 }
 
-/// Python binding to hpke-spec's Single-Shot API function hpke::HpkeSeal
+/// Generates a KEM key pair for the given (or default) cipher suite,
+/// returning `(secret_key, public_key)` bytes.
 #[pyfunction]
-fn hpke_seal<'p>(py: Python<'p>, pk_py: &PyBytes, ptxt_py: &PyBytes) -> PyResult<&'p PyBytes> {
-    let pkb = pk_py.as_bytes();
-    let ptxtb = ptxt_py.as_bytes();
-    let ciphertext_bytes = hpke_seal_bytes(pkb, ptxtb)
+#[pyo3(signature = (config=None))]
+fn generate_key_pair(
+    py: Python<'_>,
+    config: Option<PyHPKEConfig>,
+) -> PyResult<(&PyBytes, &PyBytes)> {
+    let hpke_config = config.map(|c| c.0).unwrap_or_else(default_hpke_config);
+    let (sk, pk) = generate_key_pair_bytes(hpke_config)
         .map_err(|hpke_error| PyRuntimeError::new_err(format!("{hpke_error:?}")))?;
-    Ok(PyBytes::new(py, &ciphertext_bytes))
+    Ok((PyBytes::new(py, &sk), PyBytes::new(py, &pk)))
 }
 
 /// A Python module implemented in Rust.
 #[pymodule]
 fn hpke_spec(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(hpke_seal, m)?)?;
+    m.add_function(wrap_pyfunction!(hpke_open, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_key_pair, m)?)?;
+    m.add_function(wrap_pyfunction!(setup_sender, m)?)?;
+    m.add_function(wrap_pyfunction!(setup_receiver, m)?)?;
+    m.add_class::<config::Mode>()?;
+    m.add_class::<KEM>()?;
+    m.add_class::<KDF>()?;
+    m.add_class::<AEAD>()?;
+    m.add_class::<PyHPKEConfig>()?;
+    m.add_class::<SenderContext>()?;
+    m.add_class::<ReceiverContext>()?;
     Ok(())
 }
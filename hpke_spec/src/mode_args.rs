@@ -0,0 +1,45 @@
+//! Validates that the Auth/PSK arguments a caller supplies match what the
+//! selected `Mode` actually requires, before handing them to hpke-spec.
+
+use hpke::Mode;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyResult;
+
+pub fn validate(
+    mode: Mode,
+    sender_key: Option<&[u8]>,
+    psk: Option<&[u8]>,
+    psk_id: Option<&[u8]>,
+) -> PyResult<()> {
+    let wants_auth = matches!(mode, Mode::mode_auth | Mode::mode_auth_psk);
+    let wants_psk = matches!(mode, Mode::mode_psk | Mode::mode_auth_psk);
+
+    if wants_auth && sender_key.is_none() {
+        return Err(PyValueError::new_err(
+            "Auth and AuthPSK modes require a sender key",
+        ));
+    }
+    if !wants_auth && sender_key.is_some() {
+        return Err(PyValueError::new_err(
+            "a sender key was supplied but the mode is not Auth or AuthPSK",
+        ));
+    }
+
+    if psk.is_some() != psk_id.is_some() {
+        return Err(PyValueError::new_err(
+            "psk and psk_id must both be supplied or both omitted",
+        ));
+    }
+    if wants_psk && psk.is_none() {
+        return Err(PyValueError::new_err(
+            "PSK and AuthPSK modes require both psk and psk_id",
+        ));
+    }
+    if !wants_psk && psk.is_some() {
+        return Err(PyValueError::new_err(
+            "psk/psk_id were supplied but the mode is not PSK or AuthPSK",
+        ));
+    }
+
+    Ok(())
+}